@@ -0,0 +1,87 @@
+//! Multi-day events (vacations, illness, challenges) overlaid on the habit grid.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// A note attached to a contiguous, inclusive range of days.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    pub text: String,
+    pub begin: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl Event {
+    pub fn new(text: impl Into<String>, begin: NaiveDate, end: NaiveDate) -> Event {
+        let (begin, end) = if begin <= end { (begin, end) } else { (end, begin) };
+        Event {
+            text: text.into(),
+            begin,
+            end,
+        }
+    }
+
+    /// Whether this event overlaps the inclusive range `[first, last]` at all.
+    pub fn is_in_days(&self, first: NaiveDate, last: NaiveDate) -> bool {
+        self.begin <= last && first <= self.end
+    }
+
+    /// For a week row spanning `[week_start, week_end]`, returns the
+    /// `(start_column, width)` in days of this event's bar within that week,
+    /// clipped to the week's boundaries. `None` if the event doesn't touch
+    /// the week at all.
+    pub fn span_days(&self, week_start: NaiveDate, week_end: NaiveDate) -> Option<(u32, u32)> {
+        if !self.is_in_days(week_start, week_end) {
+            return None;
+        }
+        let clipped_begin = self.begin.max(week_start);
+        let clipped_end = self.end.min(week_end);
+        let start_column = (clipped_begin - week_start).num_days() as u32;
+        let width = (clipped_end - clipped_begin).num_days() as u32 + 1;
+        Some((start_column, width))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn is_in_days_detects_overlap() {
+        let event = Event::new("trip", date(2024, 6, 10), date(2024, 6, 14));
+        assert!(event.is_in_days(date(2024, 6, 1), date(2024, 6, 11)));
+        assert!(event.is_in_days(date(2024, 6, 14), date(2024, 6, 20)));
+        assert!(!event.is_in_days(date(2024, 6, 1), date(2024, 6, 9)));
+    }
+
+    #[test]
+    fn span_days_clips_to_week_boundaries() {
+        let event = Event::new("trip", date(2024, 6, 10), date(2024, 6, 20));
+
+        // Week of Sun 2024-06-09 .. Sat 2024-06-15: event starts mid-week.
+        let (start, width) = event
+            .span_days(date(2024, 6, 9), date(2024, 6, 15))
+            .unwrap();
+        assert_eq!(start, 1);
+        assert_eq!(width, 6);
+
+        // Week of Sun 2024-06-16 .. Sat 2024-06-22: event ends mid-week.
+        let (start, width) = event
+            .span_days(date(2024, 6, 16), date(2024, 6, 22))
+            .unwrap();
+        assert_eq!(start, 0);
+        assert_eq!(width, 5);
+    }
+
+    #[test]
+    fn span_days_none_outside_week() {
+        let event = Event::new("trip", date(2024, 6, 10), date(2024, 6, 14));
+        assert!(event
+            .span_days(date(2024, 7, 1), date(2024, 7, 7))
+            .is_none());
+    }
+}
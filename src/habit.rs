@@ -0,0 +1,255 @@
+//! The `Habit` type: a named, per-day log the app tracks.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Whether a habit is tracked as a yes/no toggle or as a per-day count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HabitKind {
+    Bit,
+    Count,
+}
+
+/// The value logged for a habit on a single day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HabitValue {
+    Bit(bool),
+    Count(u32),
+}
+
+/// For a `Bit` habit, which logged value counts as a successful day:
+/// performing the habit (`Achieve`, e.g. exercise) or abstaining from it
+/// (`Avoid`, e.g. no alcohol). Ignored for `Count` habits, where any count
+/// above zero is a success.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Goal {
+    #[default]
+    Achieve,
+    Avoid,
+}
+
+/// A single tracked habit: a name, a kind, a goal polarity, and a per-day
+/// log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Habit {
+    pub name: String,
+    pub kind: HabitKind,
+    #[serde(default)]
+    pub goal: Goal,
+    pub data: HashMap<NaiveDate, HabitValue>,
+}
+
+impl Habit {
+    pub fn new(name: impl Into<String>, kind: HabitKind) -> Habit {
+        Habit {
+            name: name.into(),
+            kind,
+            goal: Goal::default(),
+            data: HashMap::new(),
+        }
+    }
+
+    /// Flips the goal polarity between `Achieve` and `Avoid`. No-op for a
+    /// `Count` habit, which has no polarity.
+    pub fn toggle_goal(&mut self) {
+        if self.kind != HabitKind::Bit {
+            return;
+        }
+        self.goal = match self.goal {
+            Goal::Achieve => Goal::Avoid,
+            Goal::Avoid => Goal::Achieve,
+        };
+    }
+
+    /// Flips whether `day` counts as done. No-op for a `Count` habit.
+    pub fn toggle_day(&mut self, day: NaiveDate) {
+        if self.kind != HabitKind::Bit {
+            return;
+        }
+        let entry = self.data.entry(day).or_insert(HabitValue::Bit(false));
+        if let HabitValue::Bit(done) = entry {
+            *done = !*done;
+        }
+    }
+
+    /// Bumps `day`'s count up by one. No-op for a `Bit` habit.
+    pub fn increment_day(&mut self, day: NaiveDate) {
+        if self.kind != HabitKind::Count {
+            return;
+        }
+        let entry = self.data.entry(day).or_insert(HabitValue::Count(0));
+        if let HabitValue::Count(count) = entry {
+            *count = count.saturating_add(1);
+        }
+    }
+
+    /// Bumps `day`'s count down by one, floored at zero. No-op for a `Bit`
+    /// habit.
+    pub fn decrement_day(&mut self, day: NaiveDate) {
+        if self.kind != HabitKind::Count {
+            return;
+        }
+        let entry = self.data.entry(day).or_insert(HabitValue::Count(0));
+        if let HabitValue::Count(count) = entry {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    /// Whether a logged value counts as a successful day, judged against
+    /// this habit's goal polarity.
+    fn is_success(&self, value: &HabitValue) -> bool {
+        Habit::value_is_success(self.goal, value)
+    }
+
+    /// Whether a logged value counts as a successful day under `goal`: a
+    /// `Bit` matches if `done` agrees with `goal` (`Achieve` wants `true`,
+    /// `Avoid` wants `false`); a `Count` is a success above zero regardless
+    /// of polarity. Used both for the streak/rate math below and for
+    /// rendering the grid, so the colors shown always agree with those
+    /// numbers.
+    pub fn value_is_success(goal: Goal, value: &HabitValue) -> bool {
+        match value {
+            HabitValue::Bit(done) => *done == (goal == Goal::Achieve),
+            HabitValue::Count(count) => *count > 0,
+        }
+    }
+
+    /// The run of consecutive successful days ending on `today`, walking
+    /// backward. Breaks as soon as a day is missing or unsuccessful.
+    pub fn current_streak(&self, today: NaiveDate) -> u32 {
+        let mut streak = 0;
+        let mut day = today;
+        while self.data.get(&day).is_some_and(|value| self.is_success(value)) {
+            streak += 1;
+            match day.pred_opt() {
+                Some(prev) => day = prev,
+                None => break,
+            }
+        }
+        streak
+    }
+
+    /// The longest run of consecutive successful calendar days anywhere in
+    /// the log.
+    pub fn longest_streak(&self) -> u32 {
+        let mut success_days: Vec<NaiveDate> = self
+            .data
+            .iter()
+            .filter(|(_, value)| self.is_success(value))
+            .map(|(day, _)| *day)
+            .collect();
+        success_days.sort();
+
+        let mut longest = 0;
+        let mut current = 0;
+        let mut prev_day: Option<NaiveDate> = None;
+        for day in success_days {
+            current = match prev_day {
+                Some(prev) if prev.succ_opt() == Some(day) => current + 1,
+                _ => 1,
+            };
+            longest = longest.max(current);
+            prev_day = Some(day);
+        }
+        longest
+    }
+
+    /// Fraction of days in the inclusive range `[first, last]` logged as a
+    /// success. `0.0` for an empty or inverted range.
+    pub fn completion_rate(&self, first: NaiveDate, last: NaiveDate) -> f64 {
+        if first > last {
+            return 0.0;
+        }
+        let total_days = (last - first).num_days() + 1;
+        let successes = self
+            .data
+            .iter()
+            .filter(|(day, value)| **day >= first && **day <= last && self.is_success(value))
+            .count();
+        successes as f64 / total_days as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn current_streak_counts_back_from_today_and_stops_at_a_gap() {
+        let mut habit = Habit::new("No Alcohol", HabitKind::Bit);
+        habit.data.insert(date(2024, 6, 8), HabitValue::Bit(true));
+        habit.data.insert(date(2024, 6, 9), HabitValue::Bit(true));
+        habit.data.insert(date(2024, 6, 10), HabitValue::Bit(true));
+        // 2024-06-07 is missing, so the streak stops at the 8th.
+
+        assert_eq!(habit.current_streak(date(2024, 6, 10)), 3);
+    }
+
+    #[test]
+    fn current_streak_is_zero_when_today_is_not_a_success() {
+        let mut habit = Habit::new("No Alcohol", HabitKind::Bit);
+        habit.data.insert(date(2024, 6, 10), HabitValue::Bit(false));
+
+        assert_eq!(habit.current_streak(date(2024, 6, 10)), 0);
+    }
+
+    #[test]
+    fn longest_streak_finds_the_longest_consecutive_run() {
+        let mut habit = Habit::new("Water", HabitKind::Count);
+        for day in 1..=3 {
+            habit
+                .data
+                .insert(date(2024, 6, day), HabitValue::Count(1));
+        }
+        habit.data.insert(date(2024, 6, 5), HabitValue::Count(0));
+        for day in 10..=15 {
+            habit
+                .data
+                .insert(date(2024, 6, day), HabitValue::Count(2));
+        }
+
+        assert_eq!(habit.longest_streak(), 6);
+    }
+
+    #[test]
+    fn longest_streak_is_zero_for_an_empty_log() {
+        let habit = Habit::new("Habit", HabitKind::Bit);
+        assert_eq!(habit.longest_streak(), 0);
+    }
+
+    #[test]
+    fn completion_rate_divides_successes_by_days_in_range() {
+        let mut habit = Habit::new("No Alcohol", HabitKind::Bit);
+        habit.data.insert(date(2024, 6, 1), HabitValue::Bit(true));
+        habit.data.insert(date(2024, 6, 2), HabitValue::Bit(false));
+        habit.data.insert(date(2024, 6, 3), HabitValue::Bit(true));
+
+        let rate = habit.completion_rate(date(2024, 6, 1), date(2024, 6, 4));
+        assert_eq!(rate, 2.0 / 4.0);
+    }
+
+    #[test]
+    fn avoid_goal_treats_false_as_success() {
+        let mut habit = Habit::new("No Alcohol", HabitKind::Bit);
+        habit.toggle_goal();
+        habit.data.insert(date(2024, 6, 1), HabitValue::Bit(false));
+        habit.data.insert(date(2024, 6, 2), HabitValue::Bit(false));
+        habit.data.insert(date(2024, 6, 3), HabitValue::Bit(true));
+
+        assert_eq!(habit.current_streak(date(2024, 6, 2)), 2);
+        let rate = habit.completion_rate(date(2024, 6, 1), date(2024, 6, 3));
+        assert_eq!(rate, 2.0 / 3.0);
+    }
+
+    #[test]
+    fn toggle_goal_is_a_no_op_for_count_habits() {
+        let mut habit = Habit::new("Water", HabitKind::Count);
+        habit.toggle_goal();
+        assert_eq!(habit.goal, Goal::Achieve);
+    }
+}
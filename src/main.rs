@@ -1,4 +1,4 @@
-use chrono::{Datelike, Days, Local, NaiveDate};
+use chrono::{Datelike, Days, Local, NaiveDate, Weekday};
 use ratatui::crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
     execute,
@@ -12,19 +12,58 @@ use ratatui::{
 };
 use std::{collections::HashMap, error::Error, io};
 
+use habit::{Goal, Habit, HabitKind, HabitValue};
+use theme::CalendarParams;
+
+mod events;
+mod habit;
+mod persistence;
+mod theme;
+
 enum ViewMode {
     Year,
     Month,
 }
 
+/// What a keypress does with the on-screen text buffer: nothing (`Normal`),
+/// feed a habit name for adding/renaming, or feed the label for a new event.
+enum InputMode {
+    Normal,
+    EditingHabitName {
+        buffer: String,
+        action: NameAction,
+    },
+    EditingEventText {
+        buffer: String,
+        begin: NaiveDate,
+        end: NaiveDate,
+    },
+}
+
+/// Which operation an in-progress name edit will commit to.
+enum NameAction {
+    Add(HabitKind),
+    Rename,
+}
+
 /// App holds the state of the application
 struct App {
-    /// A log of days where alcohol was consumed.
-    alcohol_log: HashMap<NaiveDate, bool>,
+    /// The habits being tracked.
+    habits: Vec<Habit>,
+    /// Index into `habits` of the habit currently shown.
+    selected_habit: usize,
     /// The currently selected date.
     cursor: NaiveDate,
     /// The current view mode.
     view_mode: ViewMode,
+    /// Multi-day notes/events overlaid on the habit grid.
+    events: Vec<events::Event>,
+    /// The day an in-progress event mark started on, if any.
+    pending_event_start: Option<NaiveDate>,
+    /// Colors, week-start day, and week-number display for the calendar.
+    params: CalendarParams,
+    /// Whether a habit name or event label is currently being typed.
+    input_mode: InputMode,
     /// Should the application exit?
     should_quit: bool,
 }
@@ -50,8 +89,18 @@ fn main() -> Result<(), Box<dyn Error>> {
     )?;
     terminal.show_cursor()?;
 
-    if let Err(err) = res {
-        println!("{err:?}");
+    match res {
+        Ok(app) => {
+            let state = persistence::SavedState {
+                habits: app.habits,
+                events: app.events,
+                params: app.params,
+            };
+            if let Err(err) = persistence::save_state(&state) {
+                eprintln!("Failed to save habit log: {err}");
+            }
+        }
+        Err(err) => println!("{err:?}"),
     }
 
     Ok(())
@@ -59,31 +108,145 @@ fn main() -> Result<(), Box<dyn Error>> {
 
 impl App {
     fn new() -> App {
-        // Pre-populate with some dummy data for demonstration
-        let mut alcohol_log = HashMap::new();
+        let persistence::SavedState {
+            mut habits,
+            events,
+            params,
+        } = persistence::load_state();
+        if habits.is_empty() {
+            habits.push(Habit::new("Habit", HabitKind::Bit));
+        }
         let today = Local::now().date_naive();
-        alcohol_log.insert(today.checked_sub_days(Days::new(1)).unwrap(), true);
-        alcohol_log.insert(today.checked_sub_days(Days::new(2)).unwrap(), true);
-        alcohol_log.insert(today.checked_sub_days(Days::new(5)).unwrap(), true);
-        alcohol_log.insert(today.checked_sub_days(Days::new(10)).unwrap(), true);
-        alcohol_log.insert(today.checked_sub_days(Days::new(12)).unwrap(), true);
-        alcohol_log.insert(today.checked_sub_days(Days::new(13)).unwrap(), true);
 
         App {
-            alcohol_log,
+            habits,
+            selected_habit: 0,
             cursor: today,
             view_mode: ViewMode::Year,
+            events,
+            pending_event_start: None,
+            params,
+            input_mode: InputMode::Normal,
             should_quit: false,
         }
     }
 
+    fn selected_habit(&self) -> &Habit {
+        &self.habits[self.selected_habit]
+    }
+
+    fn selected_habit_mut(&mut self) -> &mut Habit {
+        &mut self.habits[self.selected_habit]
+    }
+
     fn set_view_mode(&mut self, view_mode: ViewMode) {
         self.view_mode = view_mode;
     }
 
     fn toggle_selected_day(&mut self) {
-        let entry = self.alcohol_log.entry(self.cursor).or_insert(false);
-        *entry = !*entry;
+        let cursor = self.cursor;
+        self.selected_habit_mut().toggle_day(cursor);
+    }
+
+    fn increment_selected_day(&mut self) {
+        let cursor = self.cursor;
+        self.selected_habit_mut().increment_day(cursor);
+    }
+
+    fn decrement_selected_day(&mut self) {
+        let cursor = self.cursor;
+        self.selected_habit_mut().decrement_day(cursor);
+    }
+
+    fn next_habit(&mut self) {
+        self.selected_habit = (self.selected_habit + 1) % self.habits.len();
+    }
+
+    fn prev_habit(&mut self) {
+        self.selected_habit = (self.selected_habit + self.habits.len() - 1) % self.habits.len();
+    }
+
+    fn begin_add_habit(&mut self, kind: HabitKind) {
+        self.input_mode = InputMode::EditingHabitName {
+            buffer: String::new(),
+            action: NameAction::Add(kind),
+        };
+    }
+
+    fn begin_rename_habit(&mut self) {
+        self.input_mode = InputMode::EditingHabitName {
+            buffer: self.selected_habit().name.clone(),
+            action: NameAction::Rename,
+        };
+    }
+
+    /// Flips the selected habit's goal polarity between `Achieve` and
+    /// `Avoid`. No-op for a `Count` habit.
+    fn toggle_selected_habit_goal(&mut self) {
+        self.selected_habit_mut().toggle_goal();
+    }
+
+    fn delete_selected_habit(&mut self) {
+        if self.habits.len() > 1 {
+            self.habits.remove(self.selected_habit);
+            if self.selected_habit >= self.habits.len() {
+                self.selected_habit = self.habits.len() - 1;
+            }
+        }
+    }
+
+    fn commit_name_edit(&mut self) {
+        let input_mode = std::mem::replace(&mut self.input_mode, InputMode::Normal);
+        if let InputMode::EditingHabitName { buffer, action } = input_mode {
+            let name = buffer.trim();
+            if name.is_empty() {
+                return;
+            }
+            match action {
+                NameAction::Add(kind) => {
+                    self.habits.push(Habit::new(name, kind));
+                    self.selected_habit = self.habits.len() - 1;
+                }
+                NameAction::Rename => {
+                    self.selected_habit_mut().name = name.to_string();
+                }
+            }
+        }
+    }
+
+    fn cancel_name_edit(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// First press marks the start of an event at the cursor; the second
+    /// press, at the cursor's new position, opens the label prompt.
+    fn mark_event_boundary(&mut self) {
+        match self.pending_event_start.take() {
+            Some(begin) => {
+                self.input_mode = InputMode::EditingEventText {
+                    buffer: String::new(),
+                    begin,
+                    end: self.cursor,
+                };
+            }
+            None => self.pending_event_start = Some(self.cursor),
+        }
+    }
+
+    fn commit_event_edit(&mut self) {
+        let input_mode = std::mem::replace(&mut self.input_mode, InputMode::Normal);
+        if let InputMode::EditingEventText { buffer, begin, end } = input_mode {
+            let text = buffer.trim();
+            if text.is_empty() {
+                return;
+            }
+            self.events.push(events::Event::new(text, begin, end));
+        }
+    }
+
+    fn cancel_event_edit(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.pending_event_start = None;
     }
 
     fn move_cursor_left(&mut self) {
@@ -133,14 +296,54 @@ impl App {
     }
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
+fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<App> {
     while !app.should_quit {
         terminal.draw(|f| ui(f, &app))?;
 
         if let Event::Key(key) = event::read()? {
+            if matches!(app.input_mode, InputMode::EditingHabitName { .. }) {
+                match key.code {
+                    KeyCode::Enter => app.commit_name_edit(),
+                    KeyCode::Esc => app.cancel_name_edit(),
+                    KeyCode::Backspace => {
+                        if let InputMode::EditingHabitName { buffer, .. } = &mut app.input_mode {
+                            buffer.pop();
+                        }
+                    }
+                    KeyCode::Char(c) => {
+                        if let InputMode::EditingHabitName { buffer, .. } = &mut app.input_mode {
+                            buffer.push(c);
+                        }
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if matches!(app.input_mode, InputMode::EditingEventText { .. }) {
+                match key.code {
+                    KeyCode::Enter => app.commit_event_edit(),
+                    KeyCode::Esc => app.cancel_event_edit(),
+                    KeyCode::Backspace => {
+                        if let InputMode::EditingEventText { buffer, .. } = &mut app.input_mode {
+                            buffer.pop();
+                        }
+                    }
+                    KeyCode::Char(c) => {
+                        if let InputMode::EditingEventText { buffer, .. } = &mut app.input_mode {
+                            buffer.push(c);
+                        }
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
             match key.code {
                 KeyCode::Char('q') => app.should_quit = true,
                 KeyCode::Char(' ') => app.toggle_selected_day(),
+                KeyCode::Char('+') => app.increment_selected_day(),
+                KeyCode::Char('-') => app.decrement_selected_day(),
                 KeyCode::Left => match app.view_mode {
                     ViewMode::Year => app.move_cursor_left(),
                     ViewMode::Month => app.move_cursor_left_month(),
@@ -169,11 +372,20 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<(
                 }
                 KeyCode::Char('y') => app.set_view_mode(ViewMode::Year),
                 KeyCode::Char('m') => app.set_view_mode(ViewMode::Month),
+                KeyCode::Tab => app.next_habit(),
+                KeyCode::BackTab => app.prev_habit(),
+                KeyCode::Char('n') => app.begin_add_habit(HabitKind::Bit),
+                KeyCode::Char('N') => app.begin_add_habit(HabitKind::Count),
+                KeyCode::Char('r') => app.begin_rename_habit(),
+                KeyCode::Char('g') => app.toggle_selected_habit_goal(),
+                KeyCode::Char('x') => app.delete_selected_habit(),
+                KeyCode::Char('v') => app.mark_event_boundary(),
+                KeyCode::Esc => app.pending_event_start = None,
                 _ => {}
             }
         }
     }
-    Ok(())
+    Ok(app)
 }
 
 fn ui(f: &mut Frame, app: &App) {
@@ -184,6 +396,7 @@ fn ui(f: &mut Frame, app: &App) {
                 Constraint::Length(3), // For title
                 Constraint::Min(0),    // For the graph
                 Constraint::Length(1), // For cursor date
+                Constraint::Length(1), // For streak stats
                 Constraint::Length(3), // For instructions
                 Constraint::Length(1), // For legend
             ]
@@ -191,9 +404,15 @@ fn ui(f: &mut Frame, app: &App) {
         )
         .split(f.area());
 
+    let habit = app.selected_habit();
     let title = match app.view_mode {
-        ViewMode::Year => format!("Year {}", app.cursor.year()),
-        ViewMode::Month => format!("{} {}", app.cursor.format("%B"), app.cursor.year()),
+        ViewMode::Year => format!("{} — Year {}", habit.name, app.cursor.year()),
+        ViewMode::Month => format!(
+            "{} — {} {}",
+            habit.name,
+            app.cursor.format("%B"),
+            app.cursor.year()
+        ),
     };
     let title_block = Block::default().borders(Borders::ALL).title(title);
     f.render_widget(title_block, chunks[0]);
@@ -203,14 +422,24 @@ fn ui(f: &mut Frame, app: &App) {
         .constraints([Constraint::Length(4), Constraint::Min(0)])
         .split(chunks[1]);
 
-    let day_labels = vec!["", "Mon", "", "Wed", "", "Fri", ""];
-    let day_labels_paragraph = Paragraph::new(
-        day_labels
-            .iter()
-            .map(|s| Line::from(*s))
-            .collect::<Vec<_>>(),
-    )
-    .alignment(Alignment::Left);
+    // Week numbers line up with rows only in Month view, where each row is
+    // one calendar week; Year view lays out 12 months side by side, so the
+    // single-column gutter can't label them all.
+    // The month view's first day row sits six lines down: two borders, its
+    // title, a spacer, the weekday header and another spacer.
+    let gutter_lines: Vec<Line> = if app.params.show_weeks && matches!(app.view_mode, ViewMode::Month) {
+        std::iter::repeat(String::new())
+            .take(6)
+            .chain(week_number_labels(app.cursor, app.params.week_start))
+            .map(Line::from)
+            .collect()
+    } else {
+        vec!["", "Mon", "", "Wed", "", "Fri", ""]
+            .into_iter()
+            .map(Line::from)
+            .collect()
+    };
+    let day_labels_paragraph = Paragraph::new(gutter_lines).alignment(Alignment::Left);
     f.render_widget(day_labels_paragraph, graph_chunks[0]);
 
     let graph_block = Block::default().borders(Borders::ALL);
@@ -220,15 +449,21 @@ fn ui(f: &mut Frame, app: &App) {
     match app.view_mode {
         ViewMode::Year => {
             let habit_graph = HabitGraph {
-                data: &app.alcohol_log,
+                data: &habit.data,
+                goal: habit.goal,
+                events: &app.events,
                 cursor: app.cursor,
+                params: &app.params,
             };
             f.render_widget(habit_graph, graph_area);
         }
         ViewMode::Month => {
             let month_view = MonthView {
-                data: &app.alcohol_log,
+                data: &habit.data,
+                goal: habit.goal,
+                events: &app.events,
                 cursor: app.cursor,
+                params: &app.params,
             };
             f.render_widget(month_view, graph_area);
         }
@@ -238,30 +473,186 @@ fn ui(f: &mut Frame, app: &App) {
     let cursor_date_paragraph = Paragraph::new(cursor_date).alignment(Alignment::Center);
     f.render_widget(cursor_date_paragraph, chunks[2]);
 
+    let (period_first, period_last) = match app.view_mode {
+        ViewMode::Year => (
+            NaiveDate::from_ymd_opt(app.cursor.year(), 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(app.cursor.year(), 12, 31).unwrap(),
+        ),
+        ViewMode::Month => {
+            let first = NaiveDate::from_ymd_opt(app.cursor.year(), app.cursor.month(), 1).unwrap();
+            let (next_year, next_month) = if app.cursor.month() == 12 {
+                (app.cursor.year() + 1, 1)
+            } else {
+                (app.cursor.year(), app.cursor.month() + 1)
+            };
+            let last = NaiveDate::from_ymd_opt(next_year, next_month, 1)
+                .unwrap()
+                .checked_sub_days(Days::new(1))
+                .unwrap();
+            (first, last)
+        }
+    };
+    let stats = format!(
+        "Current streak: {} | Longest streak: {} | Completion rate: {:.0}%",
+        habit.current_streak(Local::now().date_naive()),
+        habit.longest_streak(),
+        habit.completion_rate(period_first, period_last) * 100.0
+    );
+    let stats_paragraph = Paragraph::new(stats).alignment(Alignment::Center);
+    f.render_widget(stats_paragraph, chunks[3]);
+
+    let instructions_title = match &app.input_mode {
+        InputMode::Normal => {
+            "Use arrow keys to move. Press <space> to toggle a day, <+>/<-> to count a day. Press \
+             <y> for year view, <m> for month view. Use <PageUp> and <PageDown> to switch months. \
+             Press <Tab>/<S-Tab> to switch habits, <n> to add a toggle habit, <N> to add a \
+             counted habit, <r> to rename, <g> to flip its goal, <x> to delete. Press <v> at the \
+             start and end of a range to mark an event. Press <q> to quit."
+                .to_string()
+        }
+        InputMode::EditingHabitName { buffer, action } => {
+            let verb = match action {
+                NameAction::Add(_) => "New habit name",
+                NameAction::Rename => "Rename habit to",
+            };
+            format!("{verb}: {buffer}_  (<Enter> to confirm, <Esc> to cancel)")
+        }
+        InputMode::EditingEventText { buffer, .. } => {
+            format!("Event label: {buffer}_  (<Enter> to confirm, <Esc> to cancel)")
+        }
+    };
     let instructions_block = Block::default()
         .borders(Borders::ALL)
-        .title("Use arrow keys to move. Press <space> to toggle a day. Press <y> for year view, <m> for month view. Use <PageUp> and <PageDown> to switch months. Press <q> to quit.");
-    f.render_widget(instructions_block, chunks[3]);
+        .title(instructions_title);
+    f.render_widget(instructions_block, chunks[4]);
 
     let legend = Paragraph::new(Line::from(vec![
-        Span::styled("■", Style::default().fg(Color::Red)),
-        Span::raw(" Drank | "),
-        Span::styled("■", Style::default().fg(Color::Green)),
-        Span::raw(" Didn't Drink | "),
-        Span::styled("■", Style::default().fg(Color::Yellow)),
+        Span::styled("■", Style::default().fg(app.params.done_color)),
+        Span::raw(" Success | "),
+        Span::styled("■", Style::default().fg(app.params.not_done_color)),
+        Span::raw(" No | "),
+        Span::styled("■", Style::default().fg(app.params.first_of_month_color)),
         Span::raw(" First Day of Month | "),
-        Span::styled("■", Style::default().fg(Color::Cyan)),
+        Span::styled("■", Style::default().fg(app.params.today_color)),
         Span::raw(" Today | "),
-        Span::styled("■", Style::default().fg(Color::White)),
+        Span::styled("■", Style::default().fg(app.params.cursor_color)),
         Span::raw(" Cursor"),
     ]))
     .alignment(Alignment::Center);
-    f.render_widget(legend, chunks[4]);
+    f.render_widget(legend, chunks[5]);
+}
+
+/// ISO week number of the first day of each displayed week in the cursor's
+/// month, one per row, for the left gutter reserved in `ui()`.
+fn week_number_labels(cursor: NaiveDate, week_start: Weekday) -> Vec<String> {
+    let year = cursor.year();
+    let month = cursor.month();
+    let first_day_of_month = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let (next_month_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    let last_day_of_month = NaiveDate::from_ymd_opt(next_month_year, next_month, 1)
+        .unwrap()
+        .checked_sub_days(Days::new(1))
+        .unwrap();
+
+    let mut labels = Vec::new();
+    let mut current_date = first_day_of_month;
+    let mut last_week_start = None;
+    while current_date <= last_day_of_month {
+        let offset = theme::days_since_week_start(current_date.weekday(), week_start);
+        let week_start_date = current_date.checked_sub_days(Days::new(offset as u64)).unwrap();
+        if last_week_start != Some(week_start_date) {
+            labels.push(format!("{:2}", current_date.iso_week().week()));
+            last_week_start = Some(week_start_date);
+        }
+        current_date = current_date.succ_opt().unwrap();
+    }
+    labels
+}
+
+/// Maps a count habit's magnitude to a shade, dim for zero and increasingly
+/// saturated green the higher the count.
+fn count_color(count: u32) -> Color {
+    match count {
+        0 => Color::Rgb(50, 50, 50),
+        1 => Color::Rgb(0, 90, 0),
+        2..=3 => Color::Rgb(0, 140, 0),
+        4..=6 => Color::Rgb(0, 200, 0),
+        _ => Color::Rgb(0, 255, 0),
+    }
+}
+
+/// Draws each event that touches `[first_day, last_day]` as a single
+/// highlighted bar per week row, instead of repainting day-by-day.
+fn render_event_bars(
+    buf: &mut ratatui::buffer::Buffer,
+    events: &[events::Event],
+    first_day: NaiveDate,
+    last_day: NaiveDate,
+    week_start: Weekday,
+    origin_x: u16,
+    origin_y: u16,
+    day_width: u16,
+) {
+    let week_start_offset = theme::days_since_week_start(first_day.weekday(), week_start) as u64;
+    let max_week = (last_day.day0() as u64 + week_start_offset) / 7;
+
+    for week in 0..=max_week {
+        let week_first_day = first_day
+            .checked_sub_days(Days::new(week_start_offset))
+            .unwrap()
+            .checked_add_days(Days::new(week * 7))
+            .unwrap();
+        let week_last_day = week_first_day.checked_add_days(Days::new(6)).unwrap();
+
+        for event in events {
+            let Some((col, width)) = event.span_days(week_first_day, week_last_day) else {
+                continue;
+            };
+
+            // span_days() gives the bar relative to the full week; clip it
+            // further to the days actually displayed (the current month).
+            let bar_start = week_first_day
+                .checked_add_days(Days::new(col as u64))
+                .unwrap();
+            let bar_end = bar_start
+                .checked_add_days(Days::new(width as u64 - 1))
+                .unwrap();
+            let clipped_start = bar_start.max(first_day);
+            let clipped_end = bar_end.min(last_day);
+            if clipped_start > clipped_end {
+                continue;
+            }
+
+            let col = (clipped_start - week_first_day).num_days() as u16;
+            let width = (clipped_end - clipped_start).num_days() as u16 + 1;
+            let y = origin_y + week as u16;
+
+            for day_offset in 0..width {
+                for sub_col in 0..day_width {
+                    let x = origin_x + (col + day_offset) * day_width + sub_col;
+                    let symbol = buf.get(x, y).symbol().to_string();
+                    buf.set_string(
+                        x,
+                        y,
+                        symbol,
+                        Style::default().bg(Color::Magenta).fg(Color::White),
+                    );
+                }
+            }
+        }
+    }
 }
 
 struct HabitGraph<'a> {
-    data: &'a HashMap<NaiveDate, bool>,
+    data: &'a HashMap<NaiveDate, HabitValue>,
+    goal: Goal,
+    events: &'a [events::Event],
     cursor: NaiveDate,
+    params: &'a CalendarParams,
 }
 
 impl Widget for HabitGraph<'_> {
@@ -324,25 +715,38 @@ impl HabitGraph<'_> {
 
             let mut current_date = first_day_of_month;
             while current_date <= last_day_of_month {
-                let day_of_week = current_date.weekday().num_days_from_sunday() as u16;
-                let week_number = (current_date.day0() + first_day_of_month.weekday().num_days_from_sunday()) / 7;
+                let day_of_week =
+                    theme::days_since_week_start(current_date.weekday(), self.params.week_start) as u16;
+                let week_number = (current_date.day0()
+                    + theme::days_since_week_start(
+                        first_day_of_month.weekday(),
+                        self.params.week_start,
+                    ))
+                    / 7;
 
                 let (symbol, mut color) = match self.data.get(&current_date) {
-                    Some(true) => ("■", Color::Red),
-                    Some(false) => ("■", Color::Green),
+                    Some(value @ HabitValue::Bit(_)) => {
+                        let color = if Habit::value_is_success(self.goal, value) {
+                            self.params.done_color
+                        } else {
+                            self.params.not_done_color
+                        };
+                        ("■", color)
+                    }
+                    Some(HabitValue::Count(count)) => ("■", count_color(*count)),
                     None => ("□", Color::Rgb(50, 50, 50)), // No data
                 };
 
                 if current_date.day() == 1 {
-                    color = Color::Yellow;
+                    color = self.params.first_of_month_color;
                 }
 
                 if current_date == today {
-                    color = Color::Cyan;
+                    color = self.params.today_color;
                 }
 
                 if current_date == self.cursor {
-                    color = Color::White;
+                    color = self.params.cursor_color;
                 }
 
                 buf.set_string(
@@ -354,13 +758,40 @@ impl HabitGraph<'_> {
 
                 current_date = current_date.succ_opt().unwrap();
             }
+
+            render_event_bars(
+                buf,
+                self.events,
+                first_day_of_month,
+                last_day_of_month,
+                self.params.week_start,
+                inner_area.x,
+                inner_area.y,
+                2,
+            );
         }
     }
 }
 
 struct MonthView<'a> {
-    data: &'a HashMap<NaiveDate, bool>,
+    data: &'a HashMap<NaiveDate, HabitValue>,
+    goal: Goal,
+    events: &'a [events::Event],
     cursor: NaiveDate,
+    params: &'a CalendarParams,
+}
+
+/// Three-letter label for a weekday, used for the month view's header row.
+fn weekday_label(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Mon",
+        Weekday::Tue => "Tue",
+        Weekday::Wed => "Wed",
+        Weekday::Thu => "Thu",
+        Weekday::Fri => "Fri",
+        Weekday::Sat => "Sat",
+        Weekday::Sun => "Sun",
+    }
 }
 
 impl Widget for MonthView<'_> {
@@ -398,51 +829,75 @@ impl Widget for MonthView<'_> {
             height: 7,
         };
 
-        let day_labels = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
-        for (i, label) in day_labels.iter().enumerate() {
+        let mut weekday = self.params.week_start;
+        for i in 0..7 {
             buf.set_string(
                 calendar_area.x + i as u16 * 4,
                 calendar_area.y,
-                *label,
+                weekday_label(weekday),
                 Style::default(),
             );
+            weekday = weekday.succ();
         }
 
         let mut current_date = first_day_of_month;
         while current_date <= last_day_of_month {
-            let week_day = current_date.weekday().num_days_from_sunday() as u16;
-            let week_number = (current_date.day0() + first_day_of_month.weekday().num_days_from_sunday()) / 7;
-
-            let (symbol, mut color) = match self.data.get(&current_date) {
-                Some(true) => ("■", Color::Red),
-                Some(false) => ("■", Color::Green),
-                None => (" ", Color::Rgb(50, 50, 50)),
+            let week_day =
+                theme::days_since_week_start(current_date.weekday(), self.params.week_start) as u16;
+            let week_number = (current_date.day0()
+                + theme::days_since_week_start(
+                    first_day_of_month.weekday(),
+                    self.params.week_start,
+                ))
+                / 7;
+
+            let mut color = match self.data.get(&current_date) {
+                Some(value @ HabitValue::Bit(_)) => {
+                    if Habit::value_is_success(self.goal, value) {
+                        self.params.done_color
+                    } else {
+                        self.params.not_done_color
+                    }
+                }
+                Some(HabitValue::Count(count)) => count_color(*count),
+                None => Color::Rgb(50, 50, 50),
             };
 
-            if current_date == today {
-                color = Color::Cyan;
+            if current_date.day() == 1 {
+                color = self.params.first_of_month_color;
             }
 
-            // draw the square
-            buf.set_string(
-                calendar_area.x + week_day * 4,
-                calendar_area.y + 2 + week_number as u16,
-                symbol,
-                Style::default().fg(color),
-            );
+            if current_date == today {
+                color = self.params.today_color;
+            }
 
-            // draw the day number
+            // Draw the day number with the day's color as its background,
+            // since a separately-drawn square would just be overwritten by
+            // this at the same cell.
             buf.set_string(
                 calendar_area.x + week_day * 4,
                 calendar_area.y + 2 + week_number as u16,
                 format!("{:2}", current_date.day()),
                 if current_date == self.cursor {
-                    Style::default().fg(Color::Black).bg(Color::White)
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(self.params.cursor_color)
                 } else {
-                    Style::default().fg(Color::White)
+                    Style::default().fg(Color::White).bg(color)
                 },
             );
             current_date = current_date.succ_opt().unwrap();
         }
+
+        render_event_bars(
+            buf,
+            self.events,
+            first_day_of_month,
+            last_day_of_month,
+            self.params.week_start,
+            calendar_area.x,
+            calendar_area.y + 2,
+            4,
+        );
     }
 }
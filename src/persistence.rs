@@ -0,0 +1,108 @@
+//! Loading and saving the app's persisted state so progress survives restarts.
+
+use crate::events::Event;
+use crate::habit::Habit;
+use crate::theme::CalendarParams;
+use serde::{Deserialize, Serialize};
+use std::{env, fs, io, path::PathBuf};
+
+const APP_DIR: &str = "hbittrkr";
+const LOG_FILE: &str = "log.json";
+
+/// Everything the app persists between runs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SavedState {
+    pub habits: Vec<Habit>,
+    pub events: Vec<Event>,
+    #[serde(default)]
+    pub params: CalendarParams,
+}
+
+/// Resolves the path to the persisted state file under the platform config
+/// dir (or the temp dir when there is none), creating the containing
+/// directory if it doesn't exist yet.
+pub fn log_path() -> io::Result<PathBuf> {
+    let mut dir = dirs::config_dir().unwrap_or_else(env::temp_dir);
+    dir.push(APP_DIR);
+    fs::create_dir_all(&dir)?;
+    dir.push(LOG_FILE);
+    Ok(dir)
+}
+
+/// Loads the saved state from disk. A missing or corrupt file is treated as
+/// an empty state rather than an error, so a fresh install or a hand-edited
+/// file never stops the app from starting.
+pub fn load_state() -> SavedState {
+    let Ok(path) = log_path() else {
+        return SavedState::default();
+    };
+    let Ok(contents) = fs::read_to_string(path) else {
+        return SavedState::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_default()
+}
+
+/// Saves the state to disk as JSON, with dates serialized as ISO-8601
+/// strings.
+pub fn save_state(state: &SavedState) -> io::Result<()> {
+    let path = log_path()?;
+    let contents = serde_json::to_string_pretty(state)?;
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::habit::{HabitKind, HabitValue};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn round_trips_empty_state() {
+        let state = SavedState::default();
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: SavedState = serde_json::from_str(&json).unwrap();
+        assert!(restored.habits.is_empty());
+        assert!(restored.events.is_empty());
+    }
+
+    #[test]
+    fn round_trips_populated_state() {
+        let mut habit = Habit::new("No Alcohol", HabitKind::Bit);
+        habit.data.insert(
+            NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            HabitValue::Bit(true),
+        );
+        habit.data.insert(
+            NaiveDate::from_ymd_opt(2024, 1, 16).unwrap(),
+            HabitValue::Bit(false),
+        );
+        let state = SavedState {
+            habits: vec![habit],
+            events: vec![Event::new(
+                "vacation",
+                NaiveDate::from_ymd_opt(2024, 1, 20).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 1, 25).unwrap(),
+            )],
+            params: CalendarParams::default(),
+        };
+
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: SavedState = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.habits.len(), 1);
+        assert_eq!(restored.habits[0].name, "No Alcohol");
+        assert_eq!(
+            restored.habits[0].data[&NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()],
+            HabitValue::Bit(true)
+        );
+        assert_eq!(restored.events.len(), 1);
+        assert_eq!(restored.events[0].text, "vacation");
+    }
+
+    #[test]
+    fn falls_back_to_empty_state_on_corrupt_json() {
+        let restored: SavedState = serde_json::from_str("not json").unwrap_or_default();
+        assert!(restored.habits.is_empty());
+        assert!(restored.events.is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+//! Configurable calendar appearance: colors, which weekday a week starts
+//! on, and whether to show ISO week numbers. Pulled out of the widgets so
+//! the look of the grid isn't hardcoded, and so it can be loaded from the
+//! same config file used for persistence.
+
+use chrono::Weekday;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// Colors and layout knobs threaded through `HabitGraph` and `MonthView`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalendarParams {
+    pub done_color: Color,
+    pub not_done_color: Color,
+    pub today_color: Color,
+    pub cursor_color: Color,
+    pub first_of_month_color: Color,
+    pub week_start: Weekday,
+    pub show_weeks: bool,
+}
+
+impl Default for CalendarParams {
+    fn default() -> CalendarParams {
+        CalendarParams {
+            done_color: Color::Red,
+            not_done_color: Color::Green,
+            today_color: Color::Cyan,
+            cursor_color: Color::White,
+            first_of_month_color: Color::Yellow,
+            week_start: Weekday::Sun,
+            show_weeks: false,
+        }
+    }
+}
+
+/// How many days `day` falls after `week_start`, so weeks can be laid out
+/// starting from any configured weekday rather than always Sunday.
+pub fn days_since_week_start(day: Weekday, week_start: Weekday) -> u32 {
+    (day.num_days_from_monday() + 7 - week_start.num_days_from_monday()) % 7
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_since_week_start_matches_num_days_from_sunday_when_sunday() {
+        for day in [
+            Weekday::Sun,
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+        ] {
+            assert_eq!(
+                days_since_week_start(day, Weekday::Sun),
+                day.num_days_from_sunday()
+            );
+        }
+    }
+
+    #[test]
+    fn days_since_week_start_is_zero_on_the_week_start_day() {
+        assert_eq!(days_since_week_start(Weekday::Mon, Weekday::Mon), 0);
+        assert_eq!(days_since_week_start(Weekday::Sun, Weekday::Mon), 6);
+    }
+}